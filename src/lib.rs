@@ -100,16 +100,19 @@ avoid non-`'static` references or allocations on error path and avoid showing se
 * Don't put non-`'static` references to context or the error value cannot be bubbled up easily or returned as `Error::source`.
 */
 
+use std::any::{Any, TypeId};
 use std::error::Error;
 use std::fmt::Debug;
 use std::fmt::{self, Display};
+use std::panic::Location;
 
 /// Includes `WithContext` trait, `ErrorContext`, `ErrorNoContext` types and related conversion traits and `*in_context_of*` functions
 pub mod prelude {
     pub use crate::{
         in_context_of, in_context_of_with, wrap_in_context_of, wrap_in_context_of_with,
-        ErrorContext, ErrorNoContext, MapErrorNoContext, ResultErrorWhile, ResultErrorWhileWrap,
-        ToErrorNoContext, WithContext, WrapContext,
+        Compat, ContextRoot, ErrorContext, ErrorNoContext, MapErrorNoContext,
+        ResultCompatErrorWhile, ResultErrorWhile, ResultErrorWhileHelp, ResultErrorWhileWrap,
+        ToErrorNoContext, WithContext, WithHelp, WrapContext,
     };
 }
 
@@ -117,6 +120,24 @@ pub mod prelude {
 pub trait WithContext<C> {
     type ContextError;
     fn with_context(self, context: C) -> Self::ContextError;
+
+    /// Like `with_context` but records the given source location instead of sampling it.
+    ///
+    /// This is used internally so that extension methods and `*in_context_of*` functions can
+    /// thread the location of the user-facing call site through to the created `ErrorContext`
+    /// rather than pointing it at the `map_err` closure. Types that do not record a location
+    /// (i.e. those that collect context themselves) can rely on the default implementation.
+    #[doc(hidden)]
+    fn with_context_at(
+        self,
+        context: C,
+        _location: &'static Location<'static>,
+    ) -> Self::ContextError
+    where
+        Self: Sized,
+    {
+        self.with_context(context)
+    }
 }
 
 /// Add context to error carried by another type like `Result`
@@ -133,15 +154,19 @@ where
     E: WithContext<C, ContextError = E>,
 {
     type ContextError = Self;
+    #[track_caller]
     fn error_while(self, context: C) -> Self {
-        self.map_err(|e| e.with_context(context))
+        let location = Location::caller();
+        self.map_err(|e| e.with_context_at(context, location))
     }
 
+    #[track_caller]
     fn error_while_with<F>(self, context: F) -> Self::ContextError
     where
         F: FnOnce() -> C,
     {
-        self.map_err(|e| e.with_context(context()))
+        let location = Location::caller();
+        self.map_err(|e| e.with_context_at(context(), location))
     }
 }
 
@@ -173,10 +198,21 @@ where
 
 impl<E, C> WithContext<C> for ErrorNoContext<E> {
     type ContextError = ErrorContext<E, C>;
+    #[track_caller]
     fn with_context(self, context: C) -> ErrorContext<E, C> {
+        self.with_context_at(context, Location::caller())
+    }
+
+    fn with_context_at(
+        self,
+        context: C,
+        location: &'static Location<'static>,
+    ) -> ErrorContext<E, C> {
         ErrorContext {
             error: self.0,
             context,
+            help: None,
+            location,
         }
     }
 }
@@ -204,26 +240,295 @@ impl<O, E> MapErrorNoContext<O, E> for Result<O, E> {
 }
 
 /// Wrap error value together with context information
+///
+/// The `location` field records where the context layer was attached (the user-facing call site
+/// captured via `#[track_caller]`) so that the per-layer trail survives binary stripping.
+///
+/// A context layer may also carry an optional end-user `help` suggestion of type `H` (added with
+/// `WithHelp::add_help` or `ResultErrorWhileHelp::error_while_help`). It defaults to
+/// `std::convert::Infallible` so that layers without a suggestion carry no extra type information.
 #[derive(Debug)]
-pub struct ErrorContext<E, C> {
+pub struct ErrorContext<E, C, H = std::convert::Infallible> {
     pub error: E,
     pub context: C,
+    pub help: Option<H>,
+    pub location: &'static Location<'static>,
 }
 
-impl<E, C> Display for ErrorContext<E, C>
+impl<E, C, H> Display for ErrorContext<E, C, H>
 where
     E: Display,
     C: Display,
+    H: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            // Compact chain walking every layer: `outer: inner: ...: root`. The alternate flag is
+            // propagated down so each inner `ErrorContext` contributes just its context prefix.
+            write!(f, "{}: {:#}", self.context, self.error)
+        } else {
+            // End-user facing message: this layer's context and the immediate inner error, which in
+            // turn renders its own layer. Intermediate layers stay visible; the compact `{:#}` form
+            // is the one that drops the `while .. got error:` scaffolding.
+            write!(f, "while {} got error: {}", self.context, self.error)
+        }
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+impl<T: ContextRoot> private::Sealed for T {}
+impl<E, C, H> private::Sealed for ErrorContext<E, C, H> {}
+
+/// Marker for types that can sit at the root of a context chain - the original error value one or
+/// more `ErrorContext` layers were built around.
+///
+/// It is implemented for the common standard library error and message types. Implement it for
+/// your own root error type to make it reachable through `ErrorContext::contexts`,
+/// `ErrorContext::root_cause` and the downcasting accessors.
+pub trait ContextRoot {}
+
+impl ContextRoot for std::io::Error {}
+impl ContextRoot for String {}
+impl ContextRoot for &str {}
+impl ContextRoot for Box<dyn Error> {}
+impl ContextRoot for Box<dyn Error + Send + Sync> {}
+
+/// Walks the nested `ErrorContext<ErrorContext<..>, C>` stack encoded in the type.
+///
+/// The recursion threads through the generic `error` parameter one layer at a time and bottoms out
+/// when it reaches a type that is not itself an `ErrorContext` (a [`ContextRoot`]). It is sealed and
+/// only exists to back the `contexts`, `root_cause` and `help` accessors.
+pub trait ErrorContextChain: private::Sealed {
+    #[doc(hidden)]
+    fn chain_contexts<'a>(&'a self, out: &mut Vec<&'a dyn Display>);
+    #[doc(hidden)]
+    fn chain_root_cause(&self) -> &dyn Display;
+    #[doc(hidden)]
+    fn chain_help(&self) -> Option<&dyn Display>;
+}
+
+impl<T: Display + ContextRoot> ErrorContextChain for T {
+    fn chain_contexts<'a>(&'a self, _out: &mut Vec<&'a dyn Display>) {}
+    fn chain_root_cause(&self) -> &dyn Display {
+        self
+    }
+    fn chain_help(&self) -> Option<&dyn Display> {
+        None
+    }
+}
+
+impl<E: ErrorContextChain, C: Display, H: Display> ErrorContextChain for ErrorContext<E, C, H> {
+    fn chain_contexts<'a>(&'a self, out: &mut Vec<&'a dyn Display>) {
+        out.push(&self.context);
+        self.error.chain_contexts(out);
+    }
+
+    fn chain_root_cause(&self) -> &dyn Display {
+        self.error.chain_root_cause()
+    }
+
+    fn chain_help(&self) -> Option<&dyn Display> {
+        match self.help {
+            Some(ref help) => Some(help),
+            None => self.error.chain_help(),
+        }
+    }
+}
+
+impl<E, C, H> ErrorContext<E, C, H>
+where
+    E: ErrorContextChain,
+    C: Display,
+    H: Display,
+{
+    /// Context layers from outermost to innermost as `&dyn Display`.
+    pub fn contexts(&self) -> impl Iterator<Item = &dyn Display> + '_ {
+        let mut out: Vec<&dyn Display> = Vec::new();
+        self.chain_contexts(&mut out);
+        out.into_iter()
+    }
+
+    /// The innermost error value the context layers were built around.
+    pub fn root_cause(&self) -> &dyn Display {
+        self.chain_root_cause()
+    }
+
+    /// The first end-user help suggestion present walking the layer chain from outermost to
+    /// innermost, if any layer carries one.
+    pub fn help(&self) -> Option<&dyn Display> {
+        self.chain_help()
+    }
+}
+
+/// Walks the nested stack comparing `TypeId`s so the original typed error can be recovered after
+/// layers of context have been added.
+///
+/// Like [`ErrorContextChain`] it recurses through the generic `error` parameter, bottoming out at
+/// a [`ContextRoot`]. It is sealed and backs the `downcast_ref`, `downcast` and `innermost`
+/// accessors. Every layer must be `'static` so that `TypeId`s can be compared.
+pub trait ErrorContextDowncast: private::Sealed + Any {
+    /// The concrete error value reached by peeling every `ErrorContext` layer.
+    type Innermost;
+    #[doc(hidden)]
+    fn innermost_ref(&self) -> &Self::Innermost;
+    #[doc(hidden)]
+    fn into_innermost(self) -> Self::Innermost
+    where
+        Self: Sized;
+    #[doc(hidden)]
+    fn chain_downcast_ref(&self, target: TypeId) -> Option<&dyn Any>;
+    #[doc(hidden)]
+    fn chain_downcast(self, target: TypeId) -> Result<Box<dyn Any>, Self>
+    where
+        Self: Sized;
+}
+
+impl<T: ContextRoot + Any> ErrorContextDowncast for T {
+    type Innermost = T;
+    fn innermost_ref(&self) -> &T {
+        self
+    }
+    fn into_innermost(self) -> T {
+        self
+    }
+    fn chain_downcast_ref(&self, target: TypeId) -> Option<&dyn Any> {
+        if self.type_id() == target {
+            Some(self)
+        } else {
+            None
+        }
+    }
+    fn chain_downcast(self, target: TypeId) -> Result<Box<dyn Any>, Self> {
+        if self.type_id() == target {
+            Ok(Box::new(self))
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl<E, C, H> ErrorContextDowncast for ErrorContext<E, C, H>
+where
+    E: ErrorContextDowncast,
+    C: Any,
+    H: Any,
 {
+    type Innermost = E::Innermost;
+    fn innermost_ref(&self) -> &E::Innermost {
+        self.error.innermost_ref()
+    }
+    fn into_innermost(self) -> E::Innermost {
+        self.error.into_innermost()
+    }
+    fn chain_downcast_ref(&self, target: TypeId) -> Option<&dyn Any> {
+        if self.type_id() == target {
+            Some(self)
+        } else {
+            self.error.chain_downcast_ref(target)
+        }
+    }
+    fn chain_downcast(self, target: TypeId) -> Result<Box<dyn Any>, Self> {
+        if self.type_id() == target {
+            return Ok(Box::new(self));
+        }
+        let ErrorContext {
+            error,
+            context,
+            help,
+            location,
+        } = self;
+        match error.chain_downcast(target) {
+            Ok(found) => Ok(found),
+            Err(error) => Err(ErrorContext {
+                error,
+                context,
+                help,
+                location,
+            }),
+        }
+    }
+}
+
+impl<E, C, H> ErrorContext<E, C, H>
+where
+    Self: ErrorContextDowncast,
+{
+    /// Search the nested error chain for a value of concrete type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.chain_downcast_ref(TypeId::of::<T>())
+            .and_then(|error| error.downcast_ref::<T>())
+    }
+
+    /// Consume the chain returning the contained value of concrete type `T`, or `self` unchanged
+    /// when no layer holds a `T`.
+    pub fn downcast<T: Any>(self) -> Result<T, Self> {
+        match self.chain_downcast(TypeId::of::<T>()) {
+            Ok(found) => Ok(*found.downcast::<T>().expect("TypeId matched concrete type")),
+            Err(this) => Err(this),
+        }
+    }
+
+    /// The original error value reached by peeling every `ErrorContext` layer.
+    pub fn innermost(&self) -> &<Self as ErrorContextDowncast>::Innermost {
+        self.innermost_ref()
+    }
+}
+
+/// Flattened, type-erased view of a whole nested `ErrorContext` chain.
+///
+/// Its `Display` is the full `while A got error: while B got error: root` string and its `source`
+/// is the original root error. Being `Send + Sync + 'static` it drops into `Box<dyn Error>` sinks
+/// and older APIs that do not understand the layered generic types. Produced by
+/// `ErrorContext::compat` and `ResultCompatErrorWhile::compat_error_while`.
+#[derive(Debug)]
+pub struct Compat {
+    message: String,
+    source: Box<dyn Error + Send + Sync + 'static>,
+}
+
+impl Display for Compat {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "while {} got error: {}", self.context, self.error)
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for Compat {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+impl<E, C, H> ErrorContext<E, C, H>
+where
+    E: ErrorContextChain + ErrorContextDowncast,
+    C: Display + Any,
+    H: Display + Any,
+    E::Innermost: Error + Send + Sync + 'static,
+{
+    /// Collapse the whole nested context chain into a single flattened [`Compat`] error.
+    pub fn compat(self) -> Compat {
+        let mut message = String::new();
+        for context in self.contexts() {
+            message.push_str("while ");
+            message.push_str(&context.to_string());
+            message.push_str(" got error: ");
+        }
+        message.push_str(&self.root_cause().to_string());
+        Compat {
+            message,
+            source: Box::new(self.into_innermost()),
+        }
     }
 }
 
-impl<E, C> Error for ErrorContext<E, C>
+impl<E, C, H> Error for ErrorContext<E, C, H>
 where
     E: Error,
     C: Display + Debug,
+    H: Display + Debug,
 {
     fn description(&self) -> &str {
         self.error.description()
@@ -234,12 +539,23 @@ where
     }
 }
 
-impl<E, C, C2> WithContext<C2> for ErrorContext<E, C> {
-    type ContextError = ErrorContext<ErrorContext<E, C>, C2>;
-    fn with_context(self, context: C2) -> ErrorContext<ErrorContext<E, C>, C2> {
+impl<E, C, H, C2> WithContext<C2> for ErrorContext<E, C, H> {
+    type ContextError = ErrorContext<ErrorContext<E, C, H>, C2>;
+    #[track_caller]
+    fn with_context(self, context: C2) -> ErrorContext<ErrorContext<E, C, H>, C2> {
+        self.with_context_at(context, Location::caller())
+    }
+
+    fn with_context_at(
+        self,
+        context: C2,
+        location: &'static Location<'static>,
+    ) -> ErrorContext<ErrorContext<E, C, H>, C2> {
         ErrorContext {
             error: self,
             context,
+            help: None,
+            location,
         }
     }
 }
@@ -248,14 +564,56 @@ impl<E, C, C2> WithContext<C2> for ErrorContext<E, C> {
 pub trait WrapContext<C> {
     type ContextError;
     fn wrap_context(self, context: C) -> Self::ContextError;
+
+    /// Like `wrap_context` but records the given source location instead of sampling it.
+    ///
+    /// Used internally to thread the user-facing call site through `map_err` closures.
+    #[doc(hidden)]
+    fn wrap_context_at(
+        self,
+        context: C,
+        location: &'static Location<'static>,
+    ) -> Self::ContextError;
 }
 
 impl<E, C> WrapContext<C> for E {
     type ContextError = ErrorContext<E, C>;
+    #[track_caller]
     fn wrap_context(self, context: C) -> ErrorContext<E, C> {
+        self.wrap_context_at(context, Location::caller())
+    }
+
+    fn wrap_context_at(
+        self,
+        context: C,
+        location: &'static Location<'static>,
+    ) -> ErrorContext<E, C> {
         ErrorContext {
             error: self,
             context,
+            help: None,
+            location,
+        }
+    }
+}
+
+/// Add an optional end-user help/suggestion message to an `ErrorContext` layer
+///
+/// Use this to attach actionable guidance for the person who sees the error message. The help is
+/// not shown by the `Display` impl; retrieve it separately with `ErrorContext::help`.
+pub trait WithHelp<H> {
+    type WithHelp;
+    fn add_help(self, help: H) -> Self::WithHelp;
+}
+
+impl<E, C, H0, H> WithHelp<H> for ErrorContext<E, C, H0> {
+    type WithHelp = ErrorContext<E, C, H>;
+    fn add_help(self, help: H) -> ErrorContext<E, C, H> {
+        ErrorContext {
+            error: self.error,
+            context: self.context,
+            help: Some(help),
+            location: self.location,
         }
     }
 }
@@ -272,47 +630,98 @@ impl<O, E, C> ResultErrorWhileWrap<O, E, C> for Result<O, E>
 where
     E: WrapContext<C, ContextError = ErrorContext<E, C>>,
 {
+    #[track_caller]
     fn wrap_error_while(self, context: C) -> Result<O, ErrorContext<E, C>> {
-        self.map_err(|e| e.wrap_context(context))
+        let location = Location::caller();
+        self.map_err(|e| e.wrap_context_at(context, location))
     }
 
+    #[track_caller]
     fn wrap_error_while_with<F>(self, context: F) -> Result<O, ErrorContext<E, C>>
     where
         F: FnOnce() -> C,
     {
-        self.map_err(|e| e.wrap_context(context()))
+        let location = Location::caller();
+        self.map_err(|e| e.wrap_context_at(context(), location))
+    }
+}
+
+/// `Result` extension trait to wrap error value in `ErrorContext` with context information and an
+/// optional end-user help message
+pub trait ResultErrorWhileHelp<O, E> {
+    fn error_while_help<C, H>(self, context: C, help: H) -> Result<O, ErrorContext<E, C, H>>;
+}
+
+impl<O, E> ResultErrorWhileHelp<O, E> for Result<O, E> {
+    #[track_caller]
+    fn error_while_help<C, H>(self, context: C, help: H) -> Result<O, ErrorContext<E, C, H>> {
+        let location = Location::caller();
+        self.map_err(|error| ErrorContext {
+            error,
+            context,
+            help: Some(help),
+            location,
+        })
+    }
+}
+
+/// `Result` extension trait that wraps the error value in `ErrorContext` with given context
+/// information and immediately collapses it to a flattened [`Compat`] error
+pub trait ResultCompatErrorWhile<O, E, C> {
+    fn compat_error_while(self, context: C) -> Result<O, Compat>;
+}
+
+impl<O, E, C> ResultCompatErrorWhile<O, E, C> for Result<O, E>
+where
+    E: WrapContext<C, ContextError = ErrorContext<E, C>>
+        + ErrorContextChain
+        + ErrorContextDowncast,
+    C: Display + Any,
+    E::Innermost: Error + Send + Sync + 'static,
+{
+    #[track_caller]
+    fn compat_error_while(self, context: C) -> Result<O, Compat> {
+        let location = Location::caller();
+        self.map_err(|e| e.wrap_context_at(context, location).compat())
     }
 }
 
 /// Executes closure adding context to returned error value with `.with_context(context)`
+#[track_caller]
 pub fn in_context_of<O, E, C, CE, B>(context: C, body: B) -> Result<O, CE>
 where
     E: WithContext<C, ContextError = CE>,
     B: FnOnce() -> Result<O, E>,
 {
-    body().map_err(|e| e.with_context(context))
+    let location = Location::caller();
+    body().map_err(|e| e.with_context_at(context, location))
 }
 
 /// Executes closure adding context to returned error value with `.with_context(context)` obtaining context by calling given function on error path
+#[track_caller]
 pub fn in_context_of_with<O, E, C, CE, F, M, B>(context: F, body: B) -> Result<O, CE>
 where
     F: FnOnce() -> C,
     E: WithContext<C, ContextError = CE>,
     B: FnOnce() -> Result<O, E>,
 {
-    body().map_err(|e| e.with_context(context()))
+    let location = Location::caller();
+    body().map_err(|e| e.with_context_at(context(), location))
 }
 
 /// Executes closure adding context to returned error value by wrapping it in `ErrorContext` with `.wrap_context(context)`
+#[track_caller]
 pub fn wrap_in_context_of<O, E, C, B>(context: C, body: B) -> Result<O, ErrorContext<E, C>>
 where
     E: WrapContext<C, ContextError = ErrorContext<E, C>>,
     B: FnOnce() -> Result<O, E>,
 {
-    body().map_err(|e| e.wrap_context(context))
+    let location = Location::caller();
+    body().map_err(|e| e.wrap_context_at(context, location))
 }
 
 /// Executes closure adding context to returned error value by wrapping it in `ErrorContext` with `.wrap_context(context)` obtaining context by calling given function on error path
+#[track_caller]
 pub fn wrap_in_context_of_with<O, E, C, F, B>(
     context: F,
     body: B,
@@ -322,7 +731,8 @@ where
     E: WrapContext<C, ContextError = ErrorContext<E, C>>,
     B: FnOnce() -> Result<O, E>,
 {
-    body().map_err(|e| e.wrap_context(context()))
+    let location = Location::caller();
+    body().map_err(|e| e.wrap_context_at(context(), location))
 }
 
 #[cfg(test)]
@@ -392,6 +802,7 @@ mod tests {
         use std::io::{Error, ErrorKind};
         let err: Result<(), Error> = Err(Error::new(ErrorKind::Other, "oh no!"));
 
+        // `{}` prints the context and the error it wraps, with no source location.
         assert_eq!(
             err.wrap_error_while("doing stuff".to_string())
                 .unwrap_err()
@@ -405,13 +816,21 @@ mod tests {
         use std::io::{Error, ErrorKind};
         let err: Result<(), Error> = Err(Error::new(ErrorKind::Other, "file is no good"));
 
+        let err = err
+            .wrap_error_while("opening file".to_string())
+            .wrap_error_while("processing fish sticks".to_string())
+            .unwrap_err();
+
+        // `{}` renders every layer in the terse `while .. got error:` form.
         assert_eq!(
-            err.wrap_error_while("opening file".to_string())
-                .wrap_error_while("processing fish sticks".to_string())
-                .unwrap_err()
-                .to_string(),
+            err.to_string(),
             "while processing fish sticks got error: while opening file got error: file is no good"
         );
+        // `{:#}` walks the whole nested stack.
+        assert_eq!(
+            format!("{:#}", err),
+            "processing fish sticks: opening file: file is no good"
+        );
     }
 
     #[test]
@@ -455,4 +874,139 @@ mod tests {
             "while processing fish sticks got error: while opening file got error: file is no good"
         );
     }
+
+    #[test]
+    fn test_alternate_compact_chain() {
+        use std::io::{Error, ErrorKind};
+        let err: Result<(), Error> = Err(Error::new(ErrorKind::Other, "file is no good"));
+
+        let err = err
+            .wrap_error_while("opening file".to_string())
+            .wrap_error_while("processing fish sticks".to_string())
+            .unwrap_err();
+
+        assert_eq!(
+            format!("{:#}", err),
+            "processing fish sticks: opening file: file is no good"
+        );
+    }
+
+    #[test]
+    fn test_contexts_and_root_cause() {
+        use std::io::{Error, ErrorKind};
+        let err: Result<(), Error> = Err(Error::new(ErrorKind::Other, "file is no good"));
+
+        let err = err
+            .wrap_error_while("opening file".to_string())
+            .wrap_error_while("processing fish sticks".to_string())
+            .unwrap_err();
+
+        let contexts: Vec<String> = err.contexts().map(|c| c.to_string()).collect();
+        assert_eq!(
+            contexts,
+            vec![
+                "processing fish sticks".to_string(),
+                "opening file".to_string()
+            ]
+        );
+        assert_eq!(err.root_cause().to_string(), "file is no good");
+    }
+
+    #[test]
+    fn test_help_message() {
+        use std::io::{Error, ErrorKind};
+        let err: Result<(), Error> = Err(Error::new(ErrorKind::PermissionDenied, "nope"));
+
+        let err = err
+            .error_while_help("opening file".to_string(), "try running as root")
+            .unwrap_err();
+
+        assert!(err.to_string().contains("while opening file"));
+        assert_eq!(
+            err.help().map(|h| h.to_string()),
+            Some("try running as root".to_string())
+        );
+    }
+
+    #[test]
+    fn test_help_walks_chain() {
+        use std::io::{Error, ErrorKind};
+        let err: Result<(), Error> = Err(Error::new(ErrorKind::Other, "boom"));
+
+        let inner = err
+            .error_while_help("reading".to_string(), "check permissions")
+            .unwrap_err();
+        let outer = inner.with_context("processing".to_string());
+
+        assert_eq!(
+            outer.help().map(|h| h.to_string()),
+            Some("check permissions".to_string())
+        );
+    }
+
+    #[test]
+    fn test_downcast_and_innermost() {
+        use std::io::{Error, ErrorKind};
+        let err: Result<(), Error> = Err(Error::new(ErrorKind::NotFound, "missing"));
+
+        let err = err
+            .wrap_error_while("opening file".to_string())
+            .wrap_error_while("processing".to_string())
+            .unwrap_err();
+
+        let io_err = err.downcast_ref::<Error>().expect("io::Error in chain");
+        assert_eq!(io_err.kind(), ErrorKind::NotFound);
+
+        assert_eq!(err.innermost().kind(), ErrorKind::NotFound);
+
+        assert!(err.downcast_ref::<u32>().is_none());
+
+        let io_err = err
+            .downcast::<Error>()
+            .unwrap_or_else(|_| panic!("io::Error in chain"));
+        assert_eq!(io_err.kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_compat_flattens_chain() {
+        use std::io::{Error, ErrorKind};
+        let err: Result<(), Error> = Err(Error::new(ErrorKind::Other, "file is no good"));
+
+        let compat = err
+            .wrap_error_while("opening file".to_string())
+            .wrap_error_while("processing fish sticks".to_string())
+            .unwrap_err()
+            .compat();
+
+        assert_eq!(
+            compat.to_string(),
+            "while processing fish sticks got error: while opening file got error: file is no good"
+        );
+
+        let boxed: Box<dyn std::error::Error> = Box::new(compat);
+        assert!(boxed.source().is_some());
+    }
+
+    #[test]
+    fn test_compat_error_while() {
+        use std::io::{Error, ErrorKind};
+        let err: Result<(), Error> = Err(Error::new(ErrorKind::Other, "boom"));
+
+        let compat = err
+            .compat_error_while("doing stuff".to_string())
+            .unwrap_err();
+
+        assert_eq!(compat.to_string(), "while doing stuff got error: boom");
+    }
+
+    #[test]
+    fn test_records_call_site_location() {
+        use std::io::{Error, ErrorKind};
+        let err: Result<(), Error> = Err(Error::new(ErrorKind::Other, "boom!"));
+
+        let line = line!() + 1;
+        let err = err.wrap_error_while("doing stuff".to_string()).unwrap_err();
+        assert_eq!(err.location.line(), line);
+        assert!(err.location.file().ends_with("lib.rs"));
+    }
 }